@@ -0,0 +1,467 @@
+// A small job queue sitting in front of the detector processes. Each call to
+// `process_videos` becomes its own job with its own `Child`, so several
+// batches can run side by side (bounded by `max_concurrent`) and can be
+// cancelled independently instead of all-or-nothing.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::runtime::{self, RuntimeState};
+
+#[derive(Clone, Serialize)]
+pub struct ProgressEvent {
+    pub job_id: String,
+    pub event_type: String,
+    pub video: String,
+    pub frame: u32,
+    pub total_frames: u32,
+    pub video_index: u32,
+    pub total_videos: u32,
+    pub fps: f32,
+}
+
+#[derive(Clone, Serialize)]
+struct JobCompleteEvent {
+    job_id: String,
+    reports: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+struct JobErrorEvent {
+    job_id: String,
+    error: String,
+}
+
+#[derive(Deserialize)]
+pub struct ProcessConfig {
+    pub confidence: f32,
+    pub stride: u32,
+    pub half_precision: bool,
+    pub output_dir: String,
+    pub search_prompts: Vec<String>,
+}
+
+#[derive(Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Cancelled,
+    Failed,
+    Completed,
+}
+
+struct Job {
+    status: JobStatus,
+    child: Option<Child>,
+    cancelled: bool,
+    video_count: u32,
+}
+
+#[derive(Clone, Serialize)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub status: JobStatus,
+    pub video_count: u32,
+}
+
+pub struct ProcessState {
+    jobs: Mutex<HashMap<String, Job>>,
+    /// Caps how many detector processes run at once, independent of how many
+    /// jobs are queued. Defaults to the number of available cores; overridable
+    /// at runtime via `set_max_concurrent_jobs`. This is the one semaphore for
+    /// the life of the app — resizing it adds or forgets permits in place
+    /// (see `resize_slots`) rather than swapping in a new `Semaphore`, so jobs
+    /// already parked in `slots.acquire()` are resized along with everyone
+    /// else instead of being stuck waiting on a semaphore nobody adjusts
+    /// anymore.
+    slots: Arc<Semaphore>,
+    max_concurrent: AtomicUsize,
+}
+
+impl Default for ProcessState {
+    fn default() -> Self {
+        let max_concurrent = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        ProcessState {
+            jobs: Mutex::new(HashMap::new()),
+            slots: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent: AtomicUsize::new(max_concurrent),
+        }
+    }
+}
+
+/// Grows or shrinks `slots`' permit count from `current` to `new_max`,
+/// updating `current` to match. Pulled out of the command so it can be unit
+/// tested without a `tauri::State`.
+fn resize_slots(slots: &Semaphore, current: &AtomicUsize, new_max: usize) {
+    let new_max = new_max.max(1);
+    let previous = current.swap(new_max, AtomicOrdering::SeqCst);
+    match new_max.cmp(&previous) {
+        Ordering::Greater => slots.add_permits(new_max - previous),
+        Ordering::Less => {
+            slots.forget_permits(previous - new_max);
+        }
+        Ordering::Equal => {}
+    }
+}
+
+/// Changes the job queue's concurrency cap for already-queued jobs as well
+/// as future ones: raising it immediately frees jobs parked waiting for a
+/// slot, lowering it throttles future acquisitions down to the new cap.
+#[tauri::command]
+pub fn set_max_concurrent_jobs(state: tauri::State<'_, Arc<ProcessState>>, max_concurrent: usize) {
+    resize_slots(&state.slots, &state.max_concurrent, max_concurrent);
+}
+
+/// Queues a batch of videos for processing and returns its `job_id` without
+/// waiting for processing (the first call does wait for the detector runtime
+/// to be resolved); the frontend tracks progress via `progress` events and
+/// completion via `job-complete` / `job-error` events, all tagged with
+/// `job_id`.
+#[tauri::command]
+pub async fn process_videos(
+    app: AppHandle,
+    state: tauri::State<'_, Arc<ProcessState>>,
+    runtime_state: tauri::State<'_, RuntimeState>,
+    files: Vec<String>,
+    config: ProcessConfig,
+) -> Result<String, String> {
+    let job_id = Uuid::new_v4().to_string();
+    let detector = runtime::resolve(&app, runtime_state.inner()).await?;
+
+    {
+        let mut jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+        jobs.insert(
+            job_id.clone(),
+            Job {
+                status: JobStatus::Queued,
+                child: None,
+                cancelled: false,
+                video_count: files.len() as u32,
+            },
+        );
+    }
+
+    let state: Arc<ProcessState> = state.inner().clone();
+
+    let job_id_for_task = job_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = run_job(&app, &state, &job_id_for_task, &detector, files, config).await;
+
+        let mut jobs = match state.jobs.lock() {
+            Ok(jobs) => jobs,
+            Err(_) => return,
+        };
+
+        // Jobs are removed once they reach a terminal state rather than kept
+        // around forever: the frontend learns the outcome from the
+        // `job-complete` / `job-error` event, and `get_queue_status` is meant
+        // to reflect the live queue, not a history of every job ever run.
+        match result {
+            Ok(reports) => {
+                jobs.remove(&job_id_for_task);
+                let _ = app.emit(
+                    "job-complete",
+                    JobCompleteEvent {
+                        job_id: job_id_for_task.clone(),
+                        reports,
+                    },
+                );
+            }
+            Err(error) => {
+                jobs.remove(&job_id_for_task);
+                let _ = app.emit(
+                    "job-error",
+                    JobErrorEvent {
+                        job_id: job_id_for_task.clone(),
+                        error,
+                    },
+                );
+            }
+        }
+    });
+
+    Ok(job_id)
+}
+
+async fn run_job(
+    app: &AppHandle,
+    state: &Arc<ProcessState>,
+    job_id: &str,
+    detector: &runtime::RuntimeInfo,
+    files: Vec<String>,
+    config: ProcessConfig,
+) -> Result<Vec<String>, String> {
+    // Wait for a free slot before spending a process on this job.
+    let _permit = state
+        .slots
+        .acquire()
+        .await
+        .map_err(|e| format!("Job queue closed: {}", e))?;
+
+    {
+        let mut jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+        if let Some(job) = jobs.get_mut(job_id) {
+            if job.cancelled {
+                return Err("Processing cancelled".to_string());
+            }
+            job.status = JobStatus::Running;
+        }
+    }
+
+    let mut args = vec![
+        "--json-progress".to_string(),
+        "--conf".to_string(),
+        config.confidence.to_string(),
+        "--stride".to_string(),
+        config.stride.to_string(),
+    ];
+
+    if config.half_precision {
+        args.push("--half".to_string());
+    }
+
+    if !config.output_dir.is_empty() {
+        args.push("--output".to_string());
+        args.push(config.output_dir.clone());
+    }
+
+    for prompt in &config.search_prompts {
+        args.push("--search".to_string());
+        args.push(prompt.clone());
+    }
+
+    args.extend(files.iter().cloned());
+
+    let _ = app.emit(
+        "progress",
+        ProgressEvent {
+            job_id: job_id.to_string(),
+            event_type: "status".to_string(),
+            video: format!("Starting: {}", detector.vision_script),
+            frame: 0,
+            total_frames: 0,
+            video_index: 0,
+            total_videos: files.len() as u32,
+            fps: 0.0,
+        },
+    );
+
+    let script_dir = std::path::Path::new(&detector.vision_script)
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+
+    let mut child = Command::new(&detector.python_exe)
+        .arg(&detector.vision_script)
+        .args(&args)
+        .current_dir(script_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start Python: {}", e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+
+    {
+        let mut jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+        if let Some(job) = jobs.get_mut(job_id) {
+            if job.cancelled {
+                let _ = child.kill();
+                return Err("Processing cancelled".to_string());
+            }
+            job.child = Some(child);
+        }
+    }
+
+    let stderr_thread = std::thread::spawn(move || {
+        let reader = BufReader::new(stderr);
+        let mut stderr_output = String::new();
+        for line in reader.lines().flatten() {
+            stderr_output.push_str(&line);
+            stderr_output.push('\n');
+        }
+        stderr_output
+    });
+
+    // Reading stdout line-by-line and waiting on the child both block the
+    // current thread, so they run on the blocking pool rather than tying up
+    // a Tokio worker thread for the duration of the job (which, with enough
+    // concurrent jobs, could starve command dispatch for the whole app).
+    let state_for_reader = Arc::clone(state);
+    let job_id_owned = job_id.to_string();
+    let app_for_reader = app.clone();
+
+    let (reports, status) = tokio::task::spawn_blocking(move || -> Result<(Vec<String>, std::process::ExitStatus), String> {
+        let mut reports: Vec<String> = Vec::new();
+        let reader = BufReader::new(stdout);
+
+        for line in reader.lines() {
+            {
+                let mut jobs = state_for_reader.jobs.lock().map_err(|e| e.to_string())?;
+                if let Some(job) = jobs.get_mut(&job_id_owned) {
+                    if job.cancelled {
+                        if let Some(mut c) = job.child.take() {
+                            let _ = c.kill();
+                        }
+                        return Err("Processing cancelled".to_string());
+                    }
+                }
+            }
+
+            if let Ok(line) = line {
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) {
+                    match json.get("type").and_then(|v| v.as_str()) {
+                        Some("progress") => {
+                            let event = ProgressEvent {
+                                job_id: job_id_owned.clone(),
+                                event_type: "progress".to_string(),
+                                video: json["video"].as_str().unwrap_or("").to_string(),
+                                frame: json["frame"].as_u64().unwrap_or(0) as u32,
+                                total_frames: json["total_frames"].as_u64().unwrap_or(0) as u32,
+                                video_index: json["video_index"].as_u64().unwrap_or(0) as u32,
+                                total_videos: json["total_videos"].as_u64().unwrap_or(0) as u32,
+                                fps: json["fps"].as_f64().unwrap_or(0.0) as f32,
+                            };
+                            let _ = app_for_reader.emit("progress", event);
+                        }
+                        Some("report") => {
+                            if let Some(path) = json["path"].as_str() {
+                                reports.push(path.to_string());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // `cancel_processing` can take and kill `job.child` itself between the
+        // stdout loop exiting (the child closed its pipes) and this re-lock,
+        // so a missing child isn't necessarily a bug — check `cancelled`
+        // first so a legitimately cancelled job reports "Processing
+        // cancelled" instead of the confusing "Job disappeared from the
+        // queue".
+        let mut child = {
+            let mut jobs = state_for_reader.jobs.lock().map_err(|e| e.to_string())?;
+            match jobs.get_mut(&job_id_owned) {
+                Some(job) if job.cancelled => return Err("Processing cancelled".to_string()),
+                Some(job) => job.child.take().ok_or("Job disappeared from the queue")?,
+                None => return Err("Job disappeared from the queue".to_string()),
+            }
+        };
+
+        let status = child.wait().map_err(|e| e.to_string())?;
+        Ok((reports, status))
+    })
+    .await
+    .map_err(|e| format!("Detector output reader panicked: {}", e))??;
+
+    let stderr_output = stderr_thread.join().unwrap_or_default();
+
+    let cancelled = {
+        let jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+        jobs.get(job_id).map(|job| job.cancelled).unwrap_or(false)
+    };
+
+    if !status.success() {
+        if cancelled {
+            return Err("Processing cancelled".to_string());
+        }
+        if !stderr_output.is_empty() {
+            return Err(format!("Processing failed: {}", stderr_output));
+        }
+        return Err("Processing failed".to_string());
+    }
+
+    Ok(reports)
+}
+
+/// Cancels a single job by id, or every in-flight job when `job_id` is
+/// `None` (the old all-or-nothing behavior, kept for callers that don't
+/// care about individual jobs).
+#[tauri::command]
+pub fn cancel_processing(
+    state: tauri::State<'_, Arc<ProcessState>>,
+    job_id: Option<String>,
+) -> Result<(), String> {
+    let mut jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+
+    match job_id {
+        Some(id) => {
+            if let Some(job) = jobs.get_mut(&id) {
+                job.cancelled = true;
+                if let Some(mut child) = job.child.take() {
+                    let _ = child.kill();
+                }
+            }
+        }
+        None => {
+            for job in jobs.values_mut() {
+                job.cancelled = true;
+                if let Some(mut child) = job.child.take() {
+                    let _ = child.kill();
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_queue_status(state: tauri::State<'_, Arc<ProcessState>>) -> Result<Vec<JobSummary>, String> {
+    let jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+    Ok(jobs
+        .iter()
+        .map(|(job_id, job)| JobSummary {
+            job_id: job_id.clone(),
+            status: job.status,
+            video_count: job.video_count,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn raising_the_cap_frees_a_job_already_waiting_for_a_slot() {
+        let slots = Semaphore::new(1);
+        let max_concurrent = AtomicUsize::new(1);
+
+        let _held = slots.acquire().await.unwrap();
+        let waiter = tokio::time::timeout(std::time::Duration::from_millis(50), slots.acquire());
+        assert!(waiter.await.is_err(), "should still be waiting at cap 1");
+
+        resize_slots(&slots, &max_concurrent, 2);
+
+        let freed = tokio::time::timeout(std::time::Duration::from_millis(50), slots.acquire()).await;
+        assert!(freed.is_ok(), "raising the cap should free the waiting acquire");
+    }
+
+    #[tokio::test]
+    async fn lowering_the_cap_throttles_future_acquisitions() {
+        let slots = Semaphore::new(2);
+        let max_concurrent = AtomicUsize::new(2);
+
+        resize_slots(&slots, &max_concurrent, 1);
+
+        let _first = slots.acquire().await.unwrap();
+        let second = tokio::time::timeout(std::time::Duration::from_millis(50), slots.acquire());
+        assert!(second.await.is_err(), "cap should now be 1, not 2");
+    }
+}
@@ -0,0 +1,295 @@
+// Resolves the path to the bundled Python detector, either from a sidecar
+// shipped inside the app bundle or from a versioned archive downloaded from
+// GitHub releases on first launch. The result is cached for the life of the
+// app so repeated calls (and every `process_videos` invocation) are free.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use crate::minisign;
+
+/// Repo that publishes versioned, platform-specific detector archives.
+/// Separate from the app's own release repo since the detector ships on its
+/// own cadence.
+const RUNTIME_REPO: &str = "nickpolychronakis/VisionX-detector";
+
+/// Base64-encoded minisign public key for the detector repo's release
+/// signing key. Distinct from `minisign::APP_PUBLIC_KEY`: the detector ships
+/// from a different repo on its own cadence, so it's signed with its own
+/// key rather than the app's.
+const DETECTOR_PUBLIC_KEY: &str = "RWQqct7CheXzdK8KMG9TQm9D1x3RTbCWXWYmcfUz2ZpgL6Qh3VQxN8Zy";
+const RUNTIME_CACHE_DIR: &str = "runtime";
+const RUNTIME_MANIFEST: &str = "runtime.json";
+
+/// Name declared under `externalBin` in `tauri.conf.json`. Tauri looks for
+/// this suffixed with the target triple (e.g. `vision-detector-x86_64-apple-darwin`)
+/// next to the app binary at build time.
+const SIDECAR_NAME: &str = "vision-detector";
+
+#[derive(Clone, Serialize)]
+pub struct RuntimeInfo {
+    pub python_exe: String,
+    pub vision_script: String,
+    pub version: String,
+    pub source: RuntimeSource,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeSource {
+    Sidecar,
+    Cached,
+    Downloaded,
+}
+
+/// Holds the one-time resolution of the detector runtime. `OnceCell` rather
+/// than a `Mutex<Option<_>>` so a second caller that arrives while resolution
+/// is already in flight awaits the same in-progress future instead of also
+/// seeing `None` and kicking off its own concurrent download.
+#[derive(Default)]
+pub struct RuntimeState(pub tokio::sync::OnceCell<RuntimeInfo>);
+
+#[derive(Serialize, Deserialize)]
+struct CachedManifest {
+    version: String,
+    python_exe: String,
+    vision_script: String,
+}
+
+#[tauri::command]
+pub async fn resolve_runtime(
+    app: AppHandle,
+    state: tauri::State<'_, RuntimeState>,
+) -> Result<RuntimeInfo, String> {
+    resolve(&app, &state).await
+}
+
+/// Resolves and caches the detector runtime, reusing a prior resolution if
+/// one already happened this session. Called both by the `resolve_runtime`
+/// command (so the UI can show setup status) and by `process_videos` (which
+/// just needs the path).
+///
+/// Uses `OnceCell::get_or_try_init` so concurrent callers (e.g. several
+/// `process_videos` calls fired back to back before the first resolution
+/// finishes) await the same resolution instead of each seeing an empty cache
+/// and racing their own download/extraction into the same cache dir.
+pub async fn resolve(app: &AppHandle, state: &RuntimeState) -> Result<RuntimeInfo, String> {
+    state
+        .0
+        .get_or_try_init(|| async {
+            if let Some(info) = bundled_sidecar(app)? {
+                Ok(info)
+            } else if let Some(info) = cached_download(app)? {
+                Ok(info)
+            } else {
+                download_and_cache(app).await
+            }
+        })
+        .await
+        .cloned()
+}
+
+/// Looks for a sidecar binary bundled at build time via `externalBin` in
+/// `tauri.conf.json`. This is the common case for release builds.
+///
+/// `app.shell().sidecar(SIDECAR_NAME)` only tells us the name was declared
+/// for this target (and hands back a `Command` we'd have to restructure
+/// `jobs.rs` around to actually use, since it runs jobs through plain
+/// `std::process::Child`). So we ask it for that presence check, then
+/// resolve the path the same way it would at spawn time: `tauri-build`'s
+/// `copy_binaries` strips the `-{target-triple}` suffix when it copies the
+/// sidecar next to the app executable, so the bare name (plus `.exe` on
+/// Windows) lives in `current_exe()`'s directory — not the resource dir.
+fn bundled_sidecar(app: &AppHandle) -> Result<Option<RuntimeInfo>, String> {
+    if app.shell().sidecar(SIDECAR_NAME).is_err() {
+        return Ok(None);
+    }
+
+    let python_exe = sidecar_path().map_err(|e| format!("Failed to resolve sidecar path: {}", e))?;
+
+    let resource_dir = app
+        .path()
+        .resource_dir()
+        .map_err(|e| format!("Failed to resolve resource dir: {}", e))?;
+    let vision_script = resource_dir.join("detector").join("vision.py");
+
+    if python_exe.exists() && vision_script.exists() {
+        return Ok(Some(RuntimeInfo {
+            python_exe: python_exe.to_string_lossy().to_string(),
+            vision_script: vision_script.to_string_lossy().to_string(),
+            version: app.package_info().version.to_string(),
+            source: RuntimeSource::Sidecar,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Mirrors `tauri_plugin_shell`'s own `relative_command_path`: the sidecar
+/// sits next to the current executable, not in the resource dir, and gets
+/// an `.exe` suffix appended on Windows.
+fn sidecar_path() -> std::io::Result<PathBuf> {
+    let exe_dir = std::env::current_exe()?
+        .parent()
+        .ok_or_else(|| std::io::Error::other("current executable has no parent directory"))?
+        .to_path_buf();
+
+    let mut path = exe_dir.join(SIDECAR_NAME);
+    if cfg!(windows) {
+        path.as_mut_os_string().push(".exe");
+    }
+    Ok(path)
+}
+
+/// Looks for a previously downloaded runtime in the app data dir, trusting
+/// the cached manifest written by `download_and_cache`.
+fn cached_download(app: &AppHandle) -> Result<Option<RuntimeInfo>, String> {
+    let manifest_path = runtime_cache_dir(app)?.join(RUNTIME_MANIFEST);
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+
+    let raw = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read runtime manifest: {}", e))?;
+    let manifest: CachedManifest = serde_json::from_str(&raw)
+        .map_err(|e| format!("Failed to parse runtime manifest: {}", e))?;
+
+    if !Path::new(&manifest.python_exe).exists() || !Path::new(&manifest.vision_script).exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(RuntimeInfo {
+        python_exe: manifest.python_exe,
+        vision_script: manifest.vision_script,
+        version: manifest.version,
+        source: RuntimeSource::Cached,
+    }))
+}
+
+/// Downloads the latest detector archive from GitHub releases, verifies it
+/// against its minisign signature, extracts it into the app data dir, and
+/// writes a manifest so future launches hit the `cached_download` path
+/// instead. The archive ends up executed as a subprocess for every job,
+/// so an unsigned or tampered archive is refused outright rather than
+/// extracted "just to be safe" — same bar as the `.dmg` update in
+/// `updater.rs`.
+async fn download_and_cache(app: &AppHandle) -> Result<RuntimeInfo, String> {
+    let client = reqwest::Client::new();
+    let release: serde_json::Value = client
+        .get(format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            RUNTIME_REPO
+        ))
+        .header("User-Agent", "VisionX-App")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check for detector release: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse detector release info: {}", e))?;
+
+    let version = release["tag_name"]
+        .as_str()
+        .unwrap_or("v0.0.0")
+        .trim_start_matches('v')
+        .to_string();
+
+    let asset_name = format!("detector-{}.tar.gz", platform_triple());
+    let assets = release["assets"].as_array().cloned().unwrap_or_default();
+    let download_url = assets
+        .iter()
+        .find(|a| a["name"].as_str() == Some(asset_name.as_str()))
+        .and_then(|asset| asset["browser_download_url"].as_str())
+        .ok_or_else(|| format!("No detector build published for {}", platform_triple()))?
+        .to_string();
+
+    let minisig_name = format!("{}.minisig", asset_name);
+    let minisig_url = assets
+        .iter()
+        .find(|a| a["name"].as_str() == Some(minisig_name.as_str()))
+        .and_then(|asset| asset["browser_download_url"].as_str())
+        .ok_or("No minisig signature published for the detector archive; refusing to install it unverified")?
+        .to_string();
+
+    let archive_bytes = client
+        .get(&download_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download detector: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read detector download: {}", e))?;
+
+    let minisig_contents = client
+        .get(&minisig_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download detector signature: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read detector signature: {}", e))?;
+
+    minisign::verify(&archive_bytes, &minisig_contents, DETECTOR_PUBLIC_KEY)
+        .map_err(|e| format!("Detector archive failed signature verification: {}", e))?;
+
+    let cache_dir = runtime_cache_dir(app)?;
+    std::fs::create_dir_all(&cache_dir)
+        .map_err(|e| format!("Failed to create runtime cache dir: {}", e))?;
+
+    let tar = flate2::read::GzDecoder::new(archive_bytes.as_ref());
+    tar::Archive::new(tar)
+        .unpack(&cache_dir)
+        .map_err(|e| format!("Failed to extract detector archive: {}", e))?;
+
+    let python_exe = cache_dir.join(sidecar_binary_name());
+    let vision_script = cache_dir.join("vision.py");
+
+    let manifest = CachedManifest {
+        version: version.clone(),
+        python_exe: python_exe.to_string_lossy().to_string(),
+        vision_script: vision_script.to_string_lossy().to_string(),
+    };
+    std::fs::write(
+        cache_dir.join(RUNTIME_MANIFEST),
+        serde_json::to_string(&manifest).map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| format!("Failed to write runtime manifest: {}", e))?;
+
+    Ok(RuntimeInfo {
+        python_exe: manifest.python_exe,
+        vision_script: manifest.vision_script,
+        version,
+        source: RuntimeSource::Downloaded,
+    })
+}
+
+fn runtime_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(data_dir.join(RUNTIME_CACHE_DIR))
+}
+
+fn sidecar_binary_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "python.exe"
+    } else {
+        "python3"
+    }
+}
+
+fn platform_triple() -> &'static str {
+    if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        "aarch64-apple-darwin"
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        "x86_64-apple-darwin"
+    } else if cfg!(target_os = "windows") {
+        "x86_64-pc-windows-msvc"
+    } else {
+        "x86_64-unknown-linux-gnu"
+    }
+}
@@ -0,0 +1,419 @@
+// macOS update checking and installation. We can't use `tauri_plugin_updater`
+// here since macOS builds aren't code-signed, so this talks to the GitHub
+// releases API directly, verifies the `.dmg` against its minisign signature
+// and published checksum, and streams the download with progress events
+// instead of dumping the user to a browser.
+
+use std::sync::Mutex;
+
+use futures_util::StreamExt;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+
+use crate::minisign;
+
+/// Which release track to check. Beta opts into prereleases; stable never
+/// sees them. Picked by the user in settings and threaded through to
+/// `check_for_updates` / `install_update` so a beta tester doesn't have to
+/// fight stable-only filtering logic.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseChannel {
+    Stable,
+    Beta,
+}
+
+#[derive(Clone, Serialize)]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub version: String,
+    pub current_version: String,
+    pub download_url: String,
+    pub can_auto_update: bool,
+    /// Result of checking the `.dmg` against its minisign signature:
+    /// `Some(true)` verified, `Some(false)` failed verification. `None` when
+    /// nothing was checked (no update available, the release publishes no
+    /// `.dmg.minisig`, or the platform doesn't use this path).
+    pub signature_verified: Option<bool>,
+    /// Which channel the candidate release came from, so the UI can label
+    /// a beta update as such.
+    pub channel: ReleaseChannel,
+}
+
+#[derive(Clone, Serialize)]
+struct DownloadProgressEvent {
+    bytes_downloaded: u64,
+    total_bytes: u64,
+    bytes_per_second: f64,
+}
+
+/// Tracks cancellation for the in-progress update download, mirroring the
+/// shared-mutex-with-a-flag pattern `jobs::ProcessState` uses for detector
+/// processes — there's no child process to kill here, just a flag the
+/// streaming loop checks between chunks.
+#[derive(Default)]
+pub struct DownloadState(Mutex<bool>);
+
+impl DownloadState {
+    fn reset(&self) -> Result<(), String> {
+        *self.0.lock().map_err(|e| e.to_string())? = false;
+        Ok(())
+    }
+
+    fn is_cancelled(&self) -> Result<bool, String> {
+        Ok(*self.0.lock().map_err(|e| e.to_string())?)
+    }
+}
+
+#[tauri::command]
+pub fn cancel_update_download(state: tauri::State<'_, DownloadState>) -> Result<(), String> {
+    *state.0.lock().map_err(|e| e.to_string())? = true;
+    Ok(())
+}
+
+struct MacRelease {
+    version: String,
+    channel: ReleaseChannel,
+    dmg_url: String,
+    minisig_url: Option<String>,
+    sha256_url: Option<String>,
+}
+
+/// Fetches the release list (not `/releases/latest`, which always skips
+/// prereleases) and picks the newest version available on `channel`: Beta
+/// considers every non-draft release, Stable only those not marked
+/// prerelease.
+async fn fetch_macos_release(client: &reqwest::Client, channel: ReleaseChannel) -> Result<MacRelease, String> {
+    let releases: Vec<serde_json::Value> = client
+        .get("https://api.github.com/repos/nickpolychronakis/VisionX/releases")
+        .header("User-Agent", "VisionX-App")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+
+    let best = releases
+        .iter()
+        .filter(|r| !r["draft"].as_bool().unwrap_or(false))
+        .filter(|r| channel == ReleaseChannel::Beta || !r["prerelease"].as_bool().unwrap_or(false))
+        .filter_map(|r| {
+            let tag = r["tag_name"].as_str()?;
+            let version = Version::parse(tag.trim_start_matches('v')).ok()?;
+            Some((version, r))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .ok_or("No releases found for the selected channel")?;
+
+    let (version, release) = best;
+    let release_channel = if release["prerelease"].as_bool().unwrap_or(false) {
+        ReleaseChannel::Beta
+    } else {
+        ReleaseChannel::Stable
+    };
+
+    let assets = release["assets"].as_array().cloned().unwrap_or_default();
+    let find_url = |suffix: &str| -> Option<String> {
+        assets
+            .iter()
+            .find(|a| a["name"].as_str().map(|n| n.ends_with(suffix)).unwrap_or(false))
+            .and_then(|a| a["browser_download_url"].as_str())
+            .map(|s| s.to_string())
+    };
+
+    let dmg_url = find_url(".dmg").ok_or("No .dmg asset published for this release")?;
+    let minisig_url = find_url(".dmg.minisig");
+    let sha256_url = find_url(".dmg.sha256");
+
+    Ok(MacRelease {
+        version: version.to_string(),
+        channel: release_channel,
+        dmg_url,
+        minisig_url,
+        sha256_url,
+    })
+}
+
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle, channel: ReleaseChannel) -> Result<UpdateInfo, String> {
+    let current_version = app.package_info().version.to_string();
+
+    #[cfg(target_os = "macos")]
+    {
+        let client = reqwest::Client::new();
+        let release = fetch_macos_release(&client, channel).await?;
+        let available = version_compare(&release.version, &current_version)?;
+
+        let signature_verified = if available {
+            match &release.minisig_url {
+                Some(minisig_url) => Some(verify_dmg_signature(&client, &release.dmg_url, minisig_url).await?),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        return Ok(UpdateInfo {
+            available,
+            version: release.version,
+            current_version,
+            download_url: release.dmg_url,
+            can_auto_update: false,
+            signature_verified,
+            channel: release.channel,
+        });
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        use tauri_plugin_updater::UpdaterExt;
+        let updater = app.updater().map_err(|e| format!("Updater error: {}", e))?;
+
+        match updater.check().await {
+            Ok(Some(update)) => Ok(UpdateInfo {
+                available: true,
+                version: update.version.clone(),
+                current_version,
+                download_url: String::new(),
+                can_auto_update: true,
+                signature_verified: None,
+                channel,
+            }),
+            Ok(None) => Ok(UpdateInfo {
+                available: false,
+                version: current_version.clone(),
+                current_version,
+                download_url: String::new(),
+                can_auto_update: true,
+                signature_verified: None,
+                channel,
+            }),
+            Err(e) => Err(format!("Failed to check for updates: {}", e)),
+        }
+    }
+}
+
+/// Downloads the `.dmg` and its sibling `.dmg.minisig` asset and checks the
+/// signature, returning whether it verified. Errors (network failure)
+/// bubble up as command errors rather than silently reporting "unverified",
+/// since the caller needs to know the difference between "we checked and it
+/// failed" and "we couldn't check".
+#[cfg(target_os = "macos")]
+async fn verify_dmg_signature(
+    client: &reqwest::Client,
+    dmg_url: &str,
+    minisig_url: &str,
+) -> Result<bool, String> {
+    let dmg_bytes = client
+        .get(dmg_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download update: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read update download: {}", e))?;
+
+    let minisig_contents = client
+        .get(minisig_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download update signature: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read update signature: {}", e))?;
+
+    match minisign::verify(&dmg_bytes, &minisig_contents, minisign::APP_PUBLIC_KEY) {
+        Ok(()) => Ok(true),
+        Err(_) => Ok(false),
+    }
+}
+
+/// Proper semver ordering, so `1.2.0-beta.1` doesn't get silently treated
+/// as equal to `1.2.0` the way naive dot-split integer parsing did.
+pub fn version_compare(latest: &str, current: &str) -> Result<bool, String> {
+    let latest = Version::parse(latest.trim_start_matches('v'))
+        .map_err(|e| format!("Invalid version \"{}\": {}", latest, e))?;
+    let current = Version::parse(current.trim_start_matches('v'))
+        .map_err(|e| format!("Invalid version \"{}\": {}", current, e))?;
+    Ok(latest > current)
+}
+
+#[tauri::command]
+pub async fn install_update(
+    _app: AppHandle,
+    channel: ReleaseChannel,
+    #[cfg(target_os = "macos")] download_state: tauri::State<'_, DownloadState>,
+) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let client = reqwest::Client::new();
+        let release = fetch_macos_release(&client, channel).await?;
+
+        let dmg_path = download_dmg_with_progress(&_app, &download_state, &client, &release.dmg_url).await?;
+
+        {
+            let minisig_url = release
+                .minisig_url
+                .as_ref()
+                .ok_or("No minisig signature published for this update; refusing to install it unverified")?;
+            let minisig_contents = client
+                .get(minisig_url)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to download update signature: {}", e))?
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read update signature: {}", e))?;
+
+            let dmg_bytes =
+                std::fs::read(&dmg_path).map_err(|e| format!("Failed to read downloaded update: {}", e))?;
+
+            minisign::verify(&dmg_bytes, &minisig_contents, minisign::APP_PUBLIC_KEY)
+                .map_err(|_| "Update signature verification failed; refusing to install".to_string())?;
+        }
+
+        if let Some(sha256_url) = &release.sha256_url {
+            verify_sha256(&client, sha256_url, &dmg_path).await?;
+        }
+
+        std::process::Command::new("open")
+            .arg(&dmg_path)
+            .spawn()
+            .map_err(|e| format!("Failed to open downloaded update: {}", e))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        use tauri_plugin_updater::UpdaterExt;
+        let _ = channel;
+        let updater = _app.updater().map_err(|e| format!("Updater error: {}", e))?;
+
+        if let Some(update) = updater.check().await.map_err(|e| e.to_string())? {
+            update.download_and_install(|_, _| {}, || {}).await.map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Streams the `.dmg` to a temp file, emitting `download-progress` events
+/// (bytes done, total, and the average bytes per second since the download
+/// started) on the same
+/// channel `jobs` uses for detector progress. Checked for cancellation
+/// between chunks via `DownloadState`.
+#[cfg(target_os = "macos")]
+async fn download_dmg_with_progress(
+    app: &AppHandle,
+    download_state: &DownloadState,
+    client: &reqwest::Client,
+    dmg_url: &str,
+) -> Result<std::path::PathBuf, String> {
+    download_state.reset()?;
+
+    let response = client
+        .get(dmg_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download update: {}", e))?;
+
+    let total_bytes = response.content_length().unwrap_or(0);
+    let file_name = dmg_url.rsplit('/').next().unwrap_or("VisionX-update.dmg");
+    let dest_path = std::env::temp_dir().join(file_name);
+    let mut file = std::fs::File::create(&dest_path)
+        .map_err(|e| format!("Failed to create download destination: {}", e))?;
+
+    let mut downloaded: u64 = 0;
+    let started_at = std::time::Instant::now();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        if download_state.is_cancelled()? {
+            return Err("Update download cancelled".to_string());
+        }
+
+        let chunk = chunk.map_err(|e| format!("Failed reading update download: {}", e))?;
+        std::io::Write::write_all(&mut file, &chunk).map_err(|e| format!("Failed writing update download: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        let elapsed = started_at.elapsed().as_secs_f64().max(0.001);
+        let _ = app.emit(
+            "download-progress",
+            DownloadProgressEvent {
+                bytes_downloaded: downloaded,
+                total_bytes,
+                bytes_per_second: downloaded as f64 / elapsed,
+            },
+        );
+    }
+
+    Ok(dest_path)
+}
+
+#[cfg(target_os = "macos")]
+async fn verify_sha256(client: &reqwest::Client, sha256_url: &str, dmg_path: &std::path::Path) -> Result<(), String> {
+    let expected = client
+        .get(sha256_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download checksum: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read checksum: {}", e))?;
+    let expected = expected.split_whitespace().next().unwrap_or("").trim().to_lowercase();
+
+    let bytes = std::fs::read(dmg_path).map_err(|e| format!("Failed to read downloaded update: {}", e))?;
+    let actual = format!("{:x}", Sha256::digest(&bytes));
+
+    if actual != expected {
+        return Err("Downloaded update failed checksum verification".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_version_is_available() {
+        assert!(version_compare("1.2.1", "1.2.0").unwrap());
+    }
+
+    #[test]
+    fn same_version_is_not_available() {
+        assert!(!version_compare("1.2.0", "1.2.0").unwrap());
+    }
+
+    #[test]
+    fn older_version_is_not_available() {
+        assert!(!version_compare("1.1.0", "1.2.0").unwrap());
+    }
+
+    #[test]
+    fn prerelease_is_not_treated_as_equal_to_release() {
+        assert!(!version_compare("1.2.0-beta.1", "1.2.0").unwrap());
+        assert!(version_compare("1.2.0", "1.2.0-beta.1").unwrap());
+    }
+
+    #[test]
+    fn prereleases_order_among_themselves() {
+        assert!(version_compare("1.2.0-beta.2", "1.2.0-beta.1").unwrap());
+        assert!(!version_compare("1.2.0-beta.1", "1.2.0-beta.2").unwrap());
+    }
+
+    #[test]
+    fn leading_v_is_tolerated() {
+        assert!(version_compare("v1.2.1", "v1.2.0").unwrap());
+    }
+
+    #[test]
+    fn invalid_version_is_an_error() {
+        assert!(version_compare("not-a-version", "1.2.0").is_err());
+    }
+}